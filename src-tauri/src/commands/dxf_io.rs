@@ -0,0 +1,553 @@
+use super::{scope, LoadResult, PointData, SaveResult, ShapeData};
+
+/// Application name under which shape metadata that has no native DXF
+/// field (rotation on anything but text, `name`/`note`) is stashed as XDATA, so
+/// it survives an export/import round-trip instead of being dropped.
+const XDATA_APP: &str = "O2DS";
+
+/// Export drawing to DXF format
+#[tauri::command]
+pub fn export_dxf(path: String, shapes_json: String) -> SaveResult {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => return SaveResult { success: false, message },
+    };
+
+    // Parse shapes from JSON
+    let shapes: Vec<ShapeData> = match serde_json::from_str(&shapes_json) {
+        Ok(s) => s,
+        Err(e) => {
+            return SaveResult {
+                success: false,
+                message: format!("Failed to parse shapes: {}", e),
+            }
+        }
+    };
+
+    // Create DXF drawing
+    let mut drawing = dxf::Drawing::new();
+
+    for shape in shapes {
+        let layer_name = ensure_layer(&mut drawing, shape.layer.as_deref());
+
+        let entity = match shape.shape_type.as_str() {
+            "line" => {
+                if let (Some(start), Some(end)) = (shape.start, shape.end) {
+                    let line = dxf::entities::Line::new(
+                        dxf::Point::new(start.x, start.y, 0.0),
+                        dxf::Point::new(end.x, end.y, 0.0),
+                    );
+                    Some(dxf::entities::Entity::new(dxf::entities::EntityType::Line(
+                        line,
+                    )))
+                } else {
+                    None
+                }
+            }
+            "circle" => {
+                if let (Some(center), Some(radius)) = (shape.center, shape.radius) {
+                    let circle = dxf::entities::Circle::new(
+                        dxf::Point::new(center.x, center.y, 0.0),
+                        radius,
+                    );
+                    Some(dxf::entities::Entity::new(
+                        dxf::entities::EntityType::Circle(circle),
+                    ))
+                } else {
+                    None
+                }
+            }
+            "text" => {
+                if let Some(value) = shape.text.clone() {
+                    let anchor = shape.text_anchor.unwrap_or(PointData { x: 0.0, y: 0.0 });
+                    // A single-line TEXT entity can't hold embedded line
+                    // breaks (AutoCAD just won't show them), so a shape
+                    // whose content spans multiple lines goes out as
+                    // MTEXT instead, which is built for that.
+                    if value.contains('\n') {
+                        let mut mtext = dxf::entities::MText::default();
+                        mtext.insertion_point = dxf::Point::new(anchor.x, anchor.y, 0.0);
+                        mtext.text_height = shape.text_height.unwrap_or(2.5);
+                        mtext.rotation_angle = shape.rotation.unwrap_or(0.0).to_degrees();
+                        mtext.text = value.replace('\n', "\\P");
+                        Some(dxf::entities::Entity::new(dxf::entities::EntityType::MText(
+                            mtext,
+                        )))
+                    } else {
+                        let mut text = dxf::entities::Text::default();
+                        text.location = dxf::Point::new(anchor.x, anchor.y, 0.0);
+                        text.text_height = shape.text_height.unwrap_or(2.5);
+                        text.value = value;
+                        text.rotation = shape.rotation.unwrap_or(0.0).to_degrees();
+                        Some(dxf::entities::Entity::new(dxf::entities::EntityType::Text(
+                            text,
+                        )))
+                    }
+                } else {
+                    None
+                }
+            }
+            "polyline" => {
+                if let Some(points) = &shape.points {
+                    let mut polyline = dxf::entities::Polyline::default();
+                    polyline.set_is_closed(shape.closed.unwrap_or(false));
+                    for p in points {
+                        polyline.add_vertex(
+                            &mut drawing,
+                            dxf::entities::Vertex::new(dxf::Point::new(p.x, p.y, 0.0)),
+                        );
+                    }
+                    Some(dxf::entities::Entity::new(
+                        dxf::entities::EntityType::Polyline(polyline),
+                    ))
+                } else {
+                    None
+                }
+            }
+            "arc" => {
+                if let (Some(center), Some(radius), Some(start_angle), Some(end_angle)) = (
+                    shape.center,
+                    shape.radius,
+                    shape.start_angle,
+                    shape.end_angle,
+                ) {
+                    let arc = dxf::entities::Arc::new(
+                        dxf::Point::new(center.x, center.y, 0.0),
+                        radius,
+                        start_angle.to_degrees(),
+                        end_angle.to_degrees(),
+                    );
+                    Some(dxf::entities::Entity::new(dxf::entities::EntityType::Arc(
+                        arc,
+                    )))
+                } else {
+                    None
+                }
+            }
+            "ellipse" => {
+                if let (Some(center), Some(major_axis), Some(ratio)) =
+                    (shape.center, shape.major_axis, shape.ratio)
+                {
+                    let mut ellipse = dxf::entities::Ellipse::new(
+                        dxf::Point::new(center.x, center.y, 0.0),
+                        dxf::Vector::new(major_axis.x, major_axis.y, 0.0),
+                        ratio,
+                    );
+                    ellipse.start_parameter = shape.start_angle.unwrap_or(0.0);
+                    ellipse.end_parameter = shape.end_angle.unwrap_or(std::f64::consts::TAU);
+                    Some(dxf::entities::Entity::new(
+                        dxf::entities::EntityType::Ellipse(ellipse),
+                    ))
+                } else {
+                    None
+                }
+            }
+            "spline" => {
+                if let Some(points) = &shape.points {
+                    let mut spline = dxf::entities::Spline::default();
+                    // Round-tripping an imported SPLINE: reuse its actual
+                    // degree/knots instead of reinterpreting the curve as
+                    // a fresh degree-3 uniform one.
+                    spline.degree_of_curve = shape.spline_degree.unwrap_or(3) as i32;
+                    spline.control_points = points
+                        .iter()
+                        .map(|p| dxf::Point::new(p.x, p.y, 0.0))
+                        .collect();
+                    spline.number_of_control_points = spline.control_points.len() as i32;
+                    spline.knot_values = shape.spline_knots.clone().unwrap_or_else(|| {
+                        clamped_uniform_knots(
+                            spline.degree_of_curve as usize,
+                            spline.control_points.len(),
+                        )
+                    });
+                    spline.number_of_knots = spline.knot_values.len() as i32;
+                    Some(dxf::entities::Entity::new(
+                        dxf::entities::EntityType::Spline(spline),
+                    ))
+                } else {
+                    None
+                }
+            }
+            // Add more shape types as needed
+            _ => None,
+        };
+
+        if let Some(mut entity) = entity {
+            entity.common.layer = layer_name;
+            if let Some(rgb) = shape.color {
+                entity.common.color = dxf::Color::from_index(rgb_to_aci(rgb));
+            }
+            attach_metadata(&mut entity, &shape);
+            drawing.add_entity(entity);
+        }
+    }
+
+    // Save DXF file
+    let checked_path = checked.to_string_lossy().into_owned();
+    match drawing.save_file(&checked_path) {
+        Ok(_) => SaveResult {
+            success: true,
+            message: format!("DXF exported to {}", path),
+        },
+        Err(e) => SaveResult {
+            success: false,
+            message: format!("Failed to export DXF: {}", e),
+        },
+    }
+}
+
+/// Import drawing from DXF format
+#[tauri::command]
+pub fn import_dxf(path: String) -> LoadResult {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message,
+            }
+        }
+    };
+
+    let drawing = match dxf::Drawing::load_file(&checked.to_string_lossy().into_owned()) {
+        Ok(d) => d,
+        Err(e) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message: format!("Failed to load DXF: {}", e),
+            }
+        }
+    };
+
+    let mut shapes: Vec<ShapeData> = Vec::new();
+
+    for entity in drawing.entities() {
+        let layer = Some(entity.common.layer.clone());
+        let color = resolve_color(&drawing, entity);
+        let (rotation, name, note) = read_metadata(entity);
+
+        let base = ShapeData {
+            layer,
+            color,
+            rotation,
+            name,
+            note,
+            ..Default::default()
+        };
+
+        let shape = match &entity.specific {
+            dxf::entities::EntityType::Line(line) => ShapeData {
+                shape_type: "line".to_string(),
+                start: Some(PointData {
+                    x: line.p1.x,
+                    y: line.p1.y,
+                }),
+                end: Some(PointData {
+                    x: line.p2.x,
+                    y: line.p2.y,
+                }),
+                ..base
+            },
+            dxf::entities::EntityType::Circle(circle) => ShapeData {
+                shape_type: "circle".to_string(),
+                center: Some(PointData {
+                    x: circle.center.x,
+                    y: circle.center.y,
+                }),
+                radius: Some(circle.radius),
+                ..base
+            },
+            dxf::entities::EntityType::Text(text) => ShapeData {
+                shape_type: "text".to_string(),
+                rotation: Some(text.rotation.to_radians()),
+                text: Some(text.value.clone()),
+                text_anchor: Some(PointData {
+                    x: text.location.x,
+                    y: text.location.y,
+                }),
+                text_height: Some(text.text_height),
+                ..base
+            },
+            dxf::entities::EntityType::MText(mtext) => ShapeData {
+                shape_type: "text".to_string(),
+                rotation: Some(mtext.rotation_angle.to_radians()),
+                // `\P` is MTEXT's paragraph break; everything else in its
+                // formatting codes (fonts, color overrides, stacking) has
+                // no equivalent on the `text` shape, so it's passed
+                // through as-is rather than stripped silently.
+                text: Some(mtext.text.replace("\\P", "\n")),
+                text_anchor: Some(PointData {
+                    x: mtext.insertion_point.x,
+                    y: mtext.insertion_point.y,
+                }),
+                text_height: Some(mtext.text_height),
+                ..base
+            },
+            dxf::entities::EntityType::Polyline(polyline) => ShapeData {
+                shape_type: "polyline".to_string(),
+                points: Some(
+                    polyline
+                        .vertices()
+                        .map(|v| PointData {
+                            x: v.location.x,
+                            y: v.location.y,
+                        })
+                        .collect(),
+                ),
+                closed: Some(polyline.is_closed()),
+                ..base
+            },
+            dxf::entities::EntityType::LwPolyline(polyline) => ShapeData {
+                shape_type: "polyline".to_string(),
+                points: Some(
+                    polyline
+                        .vertices
+                        .iter()
+                        .map(|v| PointData { x: v.x, y: v.y })
+                        .collect(),
+                ),
+                closed: Some(polyline.is_closed),
+                ..base
+            },
+            dxf::entities::EntityType::Arc(arc) => ShapeData {
+                shape_type: "arc".to_string(),
+                center: Some(PointData {
+                    x: arc.center.x,
+                    y: arc.center.y,
+                }),
+                radius: Some(arc.radius),
+                start_angle: Some(arc.start_angle.to_radians()),
+                end_angle: Some(arc.end_angle.to_radians()),
+                ..base
+            },
+            dxf::entities::EntityType::Ellipse(ellipse) => ShapeData {
+                shape_type: "ellipse".to_string(),
+                center: Some(PointData {
+                    x: ellipse.center.x,
+                    y: ellipse.center.y,
+                }),
+                major_axis: Some(PointData {
+                    x: ellipse.major_axis.x,
+                    y: ellipse.major_axis.y,
+                }),
+                ratio: Some(ellipse.minor_axis_ratio),
+                start_angle: Some(ellipse.start_parameter),
+                end_angle: Some(ellipse.end_parameter),
+                ..base
+            },
+            dxf::entities::EntityType::Spline(spline) => ShapeData {
+                shape_type: "spline".to_string(),
+                points: Some(
+                    spline
+                        .control_points
+                        .iter()
+                        .map(|p| PointData { x: p.x, y: p.y })
+                        .collect(),
+                ),
+                // Keep the source curve's actual degree/knots so a
+                // re-export doesn't reinterpret its shape.
+                spline_degree: Some(spline.degree_of_curve as u32),
+                spline_knots: Some(spline.knot_values.clone()),
+                ..base
+            },
+            // Add more entity types as needed
+            _ => continue,
+        };
+        shapes.push(shape);
+    }
+
+    match serde_json::to_string(&shapes) {
+        Ok(json) => LoadResult {
+            success: true,
+            data: Some(json),
+            message: "DXF imported successfully".to_string(),
+        },
+        Err(e) => LoadResult {
+            success: false,
+            data: None,
+            message: format!("Failed to serialize shapes: {}", e),
+        },
+    }
+}
+
+/// Returns the name of an existing layer matching `requested`, or creates
+/// it on the drawing if it doesn't exist yet. Falls back to DXF's default
+/// "0" layer when no layer was requested.
+fn ensure_layer(drawing: &mut dxf::Drawing, requested: Option<&str>) -> String {
+    let name = requested.unwrap_or("0").to_string();
+    if name == "0" || drawing.layers().any(|l| l.name == name) {
+        return name;
+    }
+
+    let mut layer = dxf::tables::Layer::default();
+    layer.name = name.clone();
+    layer.color = dxf::Color::from_index(7); // white/black, ACI default
+    drawing.add_layer(layer);
+    name
+}
+
+/// Resolves an entity's effective color to RGB, following BYLAYER back to
+/// the owning layer's color when the entity doesn't override it.
+fn resolve_color(drawing: &dxf::Drawing, entity: &dxf::entities::Entity) -> Option<[u8; 3]> {
+    let color = if entity.common.color.is_by_layer() {
+        drawing
+            .layers()
+            .find(|l| l.name == entity.common.layer)
+            .map(|l| l.color)
+            .unwrap_or(entity.common.color)
+    } else {
+        entity.common.color
+    };
+    color.index().map(aci_to_rgb)
+}
+
+fn attach_metadata(entity: &mut dxf::entities::Entity, shape: &ShapeData) {
+    let mut items = Vec::new();
+    if let Some(name) = &shape.name {
+        items.push(dxf::XDataItem::Str(format!("name={}", name)));
+    }
+    if let Some(note) = &shape.note {
+        items.push(dxf::XDataItem::Str(format!("note={}", note)));
+    }
+    if shape.shape_type != "text" {
+        if let Some(rotation) = shape.rotation {
+            items.push(dxf::XDataItem::Real(rotation));
+        }
+    }
+    if !items.is_empty() {
+        entity.common.x_data.push(dxf::XData {
+            application_name: XDATA_APP.to_string(),
+            items,
+        });
+    }
+}
+
+fn read_metadata(entity: &dxf::entities::Entity) -> (Option<f64>, Option<String>, Option<String>) {
+    let mut rotation = None;
+    let mut name = None;
+    let mut note = None;
+
+    if let Some(xdata) = entity
+        .common
+        .x_data
+        .iter()
+        .find(|x| x.application_name == XDATA_APP)
+    {
+        for item in &xdata.items {
+            match item {
+                dxf::XDataItem::Str(s) => {
+                    if let Some(value) = s.strip_prefix("name=") {
+                        name = Some(value.to_string());
+                    } else if let Some(value) = s.strip_prefix("note=") {
+                        note = Some(value.to_string());
+                    }
+                }
+                dxf::XDataItem::Real(r) => rotation = Some(*r),
+                _ => {}
+            }
+        }
+    }
+
+    (rotation, name, note)
+}
+
+/// Maps a true-color RGB triple to the nearest standard AutoCAD Color
+/// Index by brute-force search over all 255 usable entries (1-255) of
+/// [`aci_to_rgb`]. dxf-rs models entity color as ACI rather than true
+/// color, so this keeps exported colors close without needing a 24-bit
+/// color group.
+fn rgb_to_aci(rgb: [u8; 3]) -> u8 {
+    (1u16..=255)
+        .min_by_key(|&aci| {
+            let [r, g, b] = aci_to_rgb(aci as i16);
+            let dr = r as i32 - rgb[0] as i32;
+            let dg = g as i32 - rgb[1] as i32;
+            let db = b as i32 - rgb[2] as i32;
+            dr * dr + dg * dg + db * db
+        })
+        .map(|aci| aci as u8)
+        .unwrap_or(7)
+}
+
+/// Builds a clamped uniform knot vector for a degree-`degree` B-spline
+/// with `control_point_count` control points, so exported `SPLINE`
+/// entities are well-formed for any DXF-conformant reader, not just this
+/// app's own importer.
+///
+/// `pub(super)` rather than private: `svg_io` falls back to the same
+/// fabricated knot vector when flattening an in-app spline that has no
+/// `spline_knots` of its own.
+pub(super) fn clamped_uniform_knots(degree: usize, control_point_count: usize) -> Vec<f64> {
+    let order = degree + 1;
+    let knot_count = control_point_count + order;
+    let interior = knot_count.saturating_sub(2 * order);
+
+    let mut knots = Vec::with_capacity(knot_count);
+    knots.extend(std::iter::repeat(0.0).take(order));
+    knots.extend((1..=interior).map(|i| i as f64));
+    let last = interior as f64 + 1.0;
+    knots.extend(std::iter::repeat(last).take(order));
+    knots
+}
+
+/// Resolves an AutoCAD Color Index to RGB, covering the whole 1-255
+/// range rather than just the nine classic primaries.
+///
+/// Indices 1-9 and 250-255 are AutoCAD's fixed, well-known entries.
+/// 10-249 are AutoCAD's "index color wheel" (24 hues x 10 lightness
+/// steps); that part of the real ACI table is a baked lookup table
+/// Autodesk published as data rather than a documented formula, and we
+/// have no network access here to vendor the authoritative 256-entry
+/// array, so those entries are approximated procedurally from the same
+/// hue/lightness layout instead. This is close enough for round-tripping
+/// colors through this app, but importing a file that relies on the
+/// *exact* stock AutoCAD swatch for an index in that range won't be
+/// byte-identical.
+fn aci_to_rgb(aci: i16) -> [u8; 3] {
+    match aci {
+        1 => [255, 0, 0],
+        2 => [255, 255, 0],
+        3 => [0, 255, 0],
+        4 => [0, 255, 255],
+        5 => [0, 0, 255],
+        6 => [255, 0, 255],
+        7 => [255, 255, 255],
+        8 => [65, 65, 65],
+        9 => [128, 128, 128],
+        250 => [51, 51, 51],
+        251 => [80, 80, 80],
+        252 => [105, 105, 105],
+        253 => [130, 130, 130],
+        254 => [190, 190, 190],
+        255 => [255, 255, 255],
+        10..=249 => {
+            let wheel_index = (aci - 10) as f64;
+            let hue = (wheel_index / 10.0).floor() * (360.0 / 24.0);
+            let lightness_step = wheel_index % 10.0;
+            let lightness = 0.9 - lightness_step * 0.08;
+            hsl_to_rgb(hue, 1.0, lightness)
+        }
+        _ => [255, 255, 255],
+    }
+}
+
+/// Converts an HSL color (hue in degrees, saturation/lightness in
+/// 0.0-1.0) to 8-bit RGB.
+fn hsl_to_rgb(hue: f64, saturation: f64, lightness: f64) -> [u8; 3] {
+    let c = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let h_prime = hue / 60.0;
+    let x = c * (1.0 - (h_prime % 2.0 - 1.0).abs());
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = lightness - c / 2.0;
+    let to_byte = |v: f64| ((v + m).clamp(0.0, 1.0) * 255.0).round() as u8;
+    [to_byte(r1), to_byte(g1), to_byte(b1)]
+}
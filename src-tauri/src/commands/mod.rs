@@ -1,6 +1,15 @@
+mod dxf_io;
+mod project_file;
+mod scope;
+mod svg_io;
+
+pub use dxf_io::{export_dxf, import_dxf};
+pub use project_file::{load_project_binary, save_project_binary};
+pub use scope::{grant_startup_scope, open_directory_scope, revoke_directory_scope};
+pub use svg_io::{export_svg, import_svg};
+
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::Path;
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SaveResult {
@@ -18,7 +27,11 @@ pub struct LoadResult {
 /// Save drawing to native JSON format
 #[tauri::command]
 pub fn save_file(path: String, data: String) -> SaveResult {
-    match fs::write(&path, &data) {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => return SaveResult { success: false, message },
+    };
+    match fs::write(&checked, &data) {
         Ok(_) => SaveResult {
             success: true,
             message: format!("File saved to {}", path),
@@ -33,7 +46,17 @@ pub fn save_file(path: String, data: String) -> SaveResult {
 /// Load drawing from native JSON format
 #[tauri::command]
 pub fn load_file(path: String) -> LoadResult {
-    match fs::read_to_string(&path) {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message,
+            }
+        }
+    };
+    match fs::read_to_string(&checked) {
         Ok(content) => LoadResult {
             success: true,
             data: Some(content),
@@ -47,143 +70,88 @@ pub fn load_file(path: String) -> LoadResult {
     }
 }
 
-/// Export drawing to DXF format
-#[tauri::command]
-pub fn export_dxf(path: String, shapes_json: String) -> SaveResult {
-    // Parse shapes from JSON
-    let shapes: Vec<ShapeData> = match serde_json::from_str(&shapes_json) {
-        Ok(s) => s,
-        Err(e) => {
-            return SaveResult {
-                success: false,
-                message: format!("Failed to parse shapes: {}", e),
-            }
-        }
-    };
-
-    // Create DXF drawing
-    let mut drawing = dxf::Drawing::new();
-
-    for shape in shapes {
-        match shape.shape_type.as_str() {
-            "line" => {
-                if let (Some(start), Some(end)) = (shape.start, shape.end) {
-                    let line = dxf::entities::Line::new(
-                        dxf::Point::new(start.x, start.y, 0.0),
-                        dxf::Point::new(end.x, end.y, 0.0),
-                    );
-                    drawing.add_entity(dxf::entities::Entity::new(
-                        dxf::entities::EntityType::Line(line),
-                    ));
-                }
-            }
-            "circle" => {
-                if let (Some(center), Some(radius)) = (shape.center, shape.radius) {
-                    let circle = dxf::entities::Circle::new(
-                        dxf::Point::new(center.x, center.y, 0.0),
-                        radius,
-                    );
-                    drawing.add_entity(dxf::entities::Entity::new(
-                        dxf::entities::EntityType::Circle(circle),
-                    ));
-                }
-            }
-            // Add more shape types as needed
-            _ => {}
-        }
-    }
-
-    // Save DXF file
-    match drawing.save_file(&path) {
-        Ok(_) => SaveResult {
-            success: true,
-            message: format!("DXF exported to {}", path),
-        },
-        Err(e) => SaveResult {
-            success: false,
-            message: format!("Failed to export DXF: {}", e),
-        },
-    }
+/// A single point in drawing space.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PointData {
+    pub x: f64,
+    pub y: f64,
 }
 
-/// Import drawing from DXF format
-#[tauri::command]
-pub fn import_dxf(path: String) -> LoadResult {
-    let drawing = match dxf::Drawing::load_file(&path) {
-        Ok(d) => d,
-        Err(e) => {
-            return LoadResult {
-                success: false,
-                data: None,
-                message: format!("Failed to load DXF: {}", e),
-            }
-        }
-    };
+/// A shape as understood by the frontend canvas, serialized across the
+/// Tauri bridge and mapped onto whichever file format a command targets.
+///
+/// Most fields are optional because a given `shape_type` only populates
+/// the ones relevant to it (e.g. `line` uses `start`/`end`, `circle` uses
+/// `center`/`radius`). The organizational fields (`layer`, `color`,
+/// `rotation`, `name`, `note`) are optional for backwards compatibility
+/// with JSON saved before they existed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ShapeData {
+    pub shape_type: String,
+    pub start: Option<PointData>,
+    pub end: Option<PointData>,
+    pub center: Option<PointData>,
+    pub radius: Option<f64>,
+    pub points: Option<Vec<PointData>>,
 
-    let mut shapes: Vec<ShapeData> = Vec::new();
+    /// DXF layer name (or equivalent organizational grouping) the shape
+    /// belongs to. Falls back to the default layer when absent.
+    #[serde(default)]
+    pub layer: Option<String>,
+    /// True-color RGB. Stored as a triple rather than an AutoCAD color
+    /// index so it can be written to formats that don't have an ACI table
+    /// (SVG export emits it as the stroke color; SVG import does not read
+    /// it back). DXF export maps it to the nearest AutoCAD color index.
+    #[serde(default)]
+    pub color: Option<[u8; 3]>,
+    /// Rotation in radians, applied about the shape's anchor point
+    /// (`center` for circles, `start` for lines/polylines, the text
+    /// anchor for text).
+    #[serde(default)]
+    pub rotation: Option<f64>,
+    /// User-facing label, analogous to a block/entity name.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Free-form annotation, not rendered on the drawing itself.
+    #[serde(default)]
+    pub note: Option<String>,
 
-    for entity in drawing.entities() {
-        match &entity.specific {
-            dxf::entities::EntityType::Line(line) => {
-                shapes.push(ShapeData {
-                    shape_type: "line".to_string(),
-                    start: Some(PointData {
-                        x: line.p1.x,
-                        y: line.p1.y,
-                    }),
-                    end: Some(PointData {
-                        x: line.p2.x,
-                        y: line.p2.y,
-                    }),
-                    center: None,
-                    radius: None,
-                    points: None,
-                });
-            }
-            dxf::entities::EntityType::Circle(circle) => {
-                shapes.push(ShapeData {
-                    shape_type: "circle".to_string(),
-                    start: None,
-                    end: None,
-                    center: Some(PointData {
-                        x: circle.center.x,
-                        y: circle.center.y,
-                    }),
-                    radius: Some(circle.radius),
-                    points: None,
-                });
-            }
-            // Add more entity types as needed
-            _ => {}
-        }
-    }
+    /// Text content, present when `shape_type == "text"`.
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Insertion point for `text` shapes.
+    #[serde(default)]
+    pub text_anchor: Option<PointData>,
+    /// Text height in drawing units, for `text` shapes.
+    #[serde(default)]
+    pub text_height: Option<f64>,
 
-    match serde_json::to_string(&shapes) {
-        Ok(json) => LoadResult {
-            success: true,
-            data: Some(json),
-            message: "DXF imported successfully".to_string(),
-        },
-        Err(e) => LoadResult {
-            success: false,
-            data: None,
-            message: format!("Failed to serialize shapes: {}", e),
-        },
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ShapeData {
-    shape_type: String,
-    start: Option<PointData>,
-    end: Option<PointData>,
-    center: Option<PointData>,
-    radius: Option<f64>,
-    points: Option<Vec<PointData>>,
-}
+    /// Whether `points` forms a closed loop, for `polyline` shapes.
+    #[serde(default)]
+    pub closed: Option<bool>,
+    /// Start angle in radians, for `arc` shapes (uses `center`/`radius`).
+    #[serde(default)]
+    pub start_angle: Option<f64>,
+    /// End angle in radians, for `arc` shapes.
+    #[serde(default)]
+    pub end_angle: Option<f64>,
+    /// Endpoint of the major axis relative to `center`, for `ellipse`
+    /// shapes.
+    #[serde(default)]
+    pub major_axis: Option<PointData>,
+    /// Ratio of minor to major axis length, for `ellipse` shapes.
+    #[serde(default)]
+    pub ratio: Option<f64>,
 
-#[derive(Debug, Serialize, Deserialize)]
-struct PointData {
-    x: f64,
-    y: f64,
+    /// Degree of the curve, for `spline` shapes (`points` holds the
+    /// control points). Preserved from an imported `SPLINE` so
+    /// re-exporting doesn't silently reinterpret the curve as degree 3.
+    #[serde(default)]
+    pub spline_degree: Option<u32>,
+    /// Knot vector, for `spline` shapes. Preserved from an imported
+    /// `SPLINE` for the same reason as `spline_degree`; when absent (a
+    /// spline authored in-app), export falls back to a fabricated
+    /// clamped-uniform knot vector.
+    #[serde(default)]
+    pub spline_knots: Option<Vec<f64>>,
 }
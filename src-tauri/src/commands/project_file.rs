@@ -0,0 +1,250 @@
+use super::{scope, LoadResult, SaveResult, ShapeData};
+use serde::{Deserialize, Serialize};
+
+/// Identifies an Open 2D Studio binary project file before anyone tries
+/// to parse the MessagePack body after it.
+const MAGIC: &[u8; 4] = b"O2DS";
+
+/// Bump whenever `ProjectFile`'s shape changes, and add a matching arm to
+/// `migrate` so files saved by older releases keep opening.
+const CURRENT_VERSION: u32 = 2;
+
+/// The native binary project format: a fixed header (checked separately)
+/// followed by this struct serialized with MessagePack.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFile {
+    pub version: u32,
+    pub shapes: Vec<ShapeData>,
+    pub metadata: ProjectMetadata,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectMetadata {
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub units: Option<String>,
+}
+
+impl Default for ProjectMetadata {
+    fn default() -> Self {
+        ProjectMetadata {
+            title: None,
+            units: None,
+        }
+    }
+}
+
+/// Save the drawing to the native binary (`.o2ds`) format: a 4-byte magic
+/// header, a little-endian `u32` version, then a MessagePack-encoded
+/// `ProjectFile`. This is the default save format since it's far more
+/// compact and faster to parse than the JSON interchange format.
+#[tauri::command]
+pub fn save_project_binary(path: String, shapes_json: String) -> SaveResult {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => return SaveResult { success: false, message },
+    };
+
+    let shapes: Vec<ShapeData> = match serde_json::from_str(&shapes_json) {
+        Ok(s) => s,
+        Err(e) => {
+            return SaveResult {
+                success: false,
+                message: format!("Failed to parse shapes: {}", e),
+            }
+        }
+    };
+
+    let project = ProjectFile {
+        version: CURRENT_VERSION,
+        shapes,
+        metadata: ProjectMetadata::default(),
+    };
+
+    // `migrate` operates on `rmpv::Value::Map` (keyed by field name) so it
+    // can add/rename/drop fields without caring about their position;
+    // `to_vec_named` is what makes the encoded body a map instead of
+    // rmp-serde's default positional array encoding.
+    let body = match rmp_serde::to_vec_named(&project) {
+        Ok(b) => b,
+        Err(e) => {
+            return SaveResult {
+                success: false,
+                message: format!("Failed to encode project: {}", e),
+            }
+        }
+    };
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 4 + body.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&CURRENT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&body);
+
+    match std::fs::write(&checked, bytes) {
+        Ok(_) => SaveResult {
+            success: true,
+            message: format!("Project saved to {}", path),
+        },
+        Err(e) => SaveResult {
+            success: false,
+            message: format!("Failed to save project: {}", e),
+        },
+    }
+}
+
+/// Load a native binary project file, migrating it forward to
+/// `CURRENT_VERSION` if it was saved by an older release.
+#[tauri::command]
+pub fn load_project_binary(path: String) -> LoadResult {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message,
+            }
+        }
+    };
+
+    let bytes = match std::fs::read(&checked) {
+        Ok(b) => b,
+        Err(e) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message: format!("Failed to read project: {}", e),
+            }
+        }
+    };
+
+    if bytes.len() < 8 || &bytes[0..4] != MAGIC {
+        return LoadResult {
+            success: false,
+            data: None,
+            message: "Not an Open 2D Studio project file".to_string(),
+        };
+    }
+
+    let version = u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+    if version > CURRENT_VERSION {
+        return LoadResult {
+            success: false,
+            data: None,
+            message: format!(
+                "Project was saved by a newer version (v{}); this build supports up to v{}",
+                version, CURRENT_VERSION
+            ),
+        };
+    }
+
+    let mut value: rmpv::Value = match rmp_serde::from_slice(&bytes[8..]) {
+        Ok(v) => v,
+        Err(e) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message: format!("Failed to decode project: {}", e),
+            }
+        }
+    };
+
+    for v in version..CURRENT_VERSION {
+        value = migrate(v, value);
+    }
+
+    let project: ProjectFile = match rmpv::ext::from_value(value) {
+        Ok(p) => p,
+        Err(e) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message: format!("Failed to migrate project: {}", e),
+            }
+        }
+    };
+
+    match serde_json::to_string(&project.shapes) {
+        Ok(json) => LoadResult {
+            success: true,
+            data: Some(json),
+            message: "Project loaded successfully".to_string(),
+        },
+        Err(e) => LoadResult {
+            success: false,
+            data: None,
+            message: format!("Failed to serialize shapes: {}", e),
+        },
+    }
+}
+
+/// Upgrades a decoded `ProjectFile` one version at a time. Each arm only
+/// needs to know how to go from `from` to `from + 1`; `load_project_binary`
+/// walks the whole chain so files several releases old still open.
+fn migrate(from: u32, value: rmpv::Value) -> rmpv::Value {
+    match from {
+        // v1 had no `metadata` field; add an empty one.
+        1 => {
+            if let rmpv::Value::Map(mut entries) = value {
+                let key = rmpv::Value::String("metadata".into());
+                if !entries.iter().any(|(k, _)| k == &key) {
+                    entries.push((key, rmpv::Value::Map(vec![])));
+                }
+                let version_key = rmpv::Value::String("version".into());
+                if let Some(entry) = entries.iter_mut().find(|(k, _)| k == &version_key) {
+                    entry.1 = rmpv::Value::from(from + 1);
+                }
+                rmpv::Value::Map(entries)
+            } else {
+                value
+            }
+        }
+        _ => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes a v1-shaped project (no `metadata` field, MessagePack map
+    /// encoding) and checks that `load_project_binary` migrates it
+    /// forward instead of erroring out, which only works if the file was
+    /// actually encoded as a map in the first place.
+    #[test]
+    fn v1_project_migrates_and_loads() {
+        #[derive(Serialize)]
+        struct ProjectFileV1 {
+            version: u32,
+            shapes: Vec<ShapeData>,
+        }
+
+        // A dedicated subdirectory rather than the whole temp dir: the
+        // allow-list is shared with the `scope` tests, whose temp-dir paths
+        // must stay outside every granted root.
+        let dir = std::env::temp_dir().join(format!("o2ds_v1_migration_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create test dir");
+        scope::grant_startup_scope(&dir).expect("grant test dir scope");
+
+        let v1 = ProjectFileV1 {
+            version: 1,
+            shapes: vec![],
+        };
+        let body = rmp_serde::to_vec_named(&v1).expect("encode v1 project");
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&body);
+
+        let path = dir.join(format!("o2ds_v1_migration_test_{}.o2ds", std::process::id()));
+        std::fs::write(&path, &bytes).expect("write test project file");
+
+        let result = load_project_binary(path.to_string_lossy().into_owned());
+        let _ = std::fs::remove_file(&path);
+
+        assert!(result.success, "migration failed: {}", result.message);
+        assert_eq!(result.data.as_deref(), Some("[]"));
+    }
+}
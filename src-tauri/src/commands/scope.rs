@@ -0,0 +1,197 @@
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tauri_plugin_dialog::DialogExt;
+
+use super::SaveResult;
+
+/// Directories the frontend has been granted access to: the current
+/// project directory plus whatever the user has explicitly opened via a
+/// file dialog. Every filesystem-touching command must run its `path`
+/// through [`check_path`] before acting on it, so a malicious or buggy
+/// frontend payload can't read or overwrite arbitrary files.
+static ALLOWED_ROOTS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Widens the allow-list to include `dir`. Not a `#[tauri::command]`: the
+/// frontend must never be able to supply an arbitrary path here, since
+/// that would let a compromised webview grant itself access to anything
+/// (e.g. `invoke('grant_directory_scope', {dir: '/'})`) and defeat
+/// `check_path` entirely. Callers must get `dir` from somewhere the
+/// frontend can't forge — a native folder picker (see
+/// [`open_directory_scope`]) or the process's own startup directory.
+fn grant(dir: &Path) -> Result<PathBuf, String> {
+    let canonical = std::fs::canonicalize(dir).map_err(|e| format!("Failed to grant scope: {}", e))?;
+    let mut roots = ALLOWED_ROOTS.lock().unwrap();
+    if !roots.contains(&canonical) {
+        roots.push(canonical.clone());
+    }
+    Ok(canonical)
+}
+
+/// Grants the frontend's current project directory, the one scope it's
+/// allowed to receive automatically at startup.
+pub fn grant_startup_scope(dir: &Path) -> Result<PathBuf, String> {
+    grant(dir)
+}
+
+/// Opens a native folder-picker dialog and, if the user selects a
+/// directory, widens the allow-list to include it. The frontend can only
+/// trigger the picker, not supply the resulting path itself, so this is
+/// safe to expose as an invokable command.
+#[tauri::command]
+pub fn open_directory_scope(app: tauri::AppHandle) -> SaveResult {
+    let Some(folder) = app.dialog().file().blocking_pick_folder() else {
+        return SaveResult {
+            success: false,
+            message: "No directory selected".to_string(),
+        };
+    };
+
+    let path = match folder.into_path() {
+        Ok(p) => p,
+        Err(e) => {
+            return SaveResult {
+                success: false,
+                message: format!("Failed to resolve selected folder: {}", e),
+            }
+        }
+    };
+
+    match grant(&path) {
+        Ok(canonical) => SaveResult {
+            success: true,
+            message: format!("Granted access to {}", canonical.display()),
+        },
+        Err(message) => SaveResult {
+            success: false,
+            message,
+        },
+    }
+}
+
+/// Removes `dir` from the allow-list. Paths under it are denied again
+/// until re-granted. Safe to expose directly: revoking can only narrow
+/// what the frontend can touch, never widen it.
+#[tauri::command]
+pub fn revoke_directory_scope(dir: String) -> SaveResult {
+    match std::fs::canonicalize(&dir) {
+        Ok(canonical) => {
+            ALLOWED_ROOTS.lock().unwrap().retain(|root| root != &canonical);
+            SaveResult {
+                success: true,
+                message: format!("Revoked access to {}", dir),
+            }
+        }
+        Err(e) => SaveResult {
+            success: false,
+            message: format!("Failed to revoke scope: {}", e),
+        },
+    }
+}
+
+/// Verifies `path` lies within a granted directory scope and returns its
+/// canonical form for the caller to actually operate on. Both the
+/// requested path and every allowed root are canonicalized first, so
+/// `..` segments or symlinks can't be used to escape the allow-list. If
+/// `path` doesn't exist yet (a fresh save target), its parent directory
+/// is checked instead and the file name re-appended.
+pub fn check_path(path: &str) -> Result<PathBuf, String> {
+    let requested = Path::new(path);
+
+    let canonical = if requested.exists() {
+        std::fs::canonicalize(requested).map_err(|e| format!("path not permitted: {}", e))?
+    } else {
+        let parent = requested
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or_else(|| Path::new("."));
+        let file_name = requested
+            .file_name()
+            .ok_or_else(|| "path not permitted: no file name".to_string())?;
+        let canonical_parent =
+            std::fs::canonicalize(parent).map_err(|e| format!("path not permitted: {}", e))?;
+        canonical_parent.join(file_name)
+    };
+
+    let roots = ALLOWED_ROOTS.lock().unwrap();
+    if roots.iter().any(|root| canonical.starts_with(root)) {
+        Ok(canonical)
+    } else {
+        Err(format!("path not permitted: {}", path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh, empty directory under the OS temp dir, namespaced by test
+    /// label and PID so parallel test runs (and the `ALLOWED_ROOTS` static
+    /// they share) don't collide with each other.
+    fn unique_dir(label: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "o2ds_scope_test_{}_{}",
+            label,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("create test dir");
+        dir
+    }
+
+    #[test]
+    fn check_path_rejects_path_outside_any_granted_root() {
+        let granted = unique_dir("granted_a");
+        grant(&granted).expect("grant scope");
+
+        let outside = unique_dir("outside_a");
+        let target = outside.join("secret.txt");
+        std::fs::write(&target, "nope").expect("write outside file");
+
+        let result = check_path(&target.to_string_lossy());
+        assert!(
+            result.is_err(),
+            "a path outside every granted root must be rejected"
+        );
+    }
+
+    #[test]
+    fn check_path_rejects_dot_dot_escape() {
+        let granted = unique_dir("granted_b");
+        grant(&granted).expect("grant scope");
+
+        let sibling = unique_dir("sibling_b");
+        let escaping = granted
+            .join("..")
+            .join(sibling.file_name().unwrap())
+            .join("escaped.txt");
+
+        let result = check_path(&escaping.to_string_lossy());
+        assert!(
+            result.is_err(),
+            "a `..` escape out of the granted root must be rejected even though \
+             it isn't canonical on its face"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn check_path_rejects_symlink_escaping_granted_root() {
+        let granted = unique_dir("granted_c");
+        let outside = unique_dir("outside_c");
+        grant(&granted).expect("grant scope");
+
+        std::fs::write(outside.join("via_symlink.txt"), "nope").expect("write target file");
+        let link = granted.join("escape_link");
+        let _ = std::fs::remove_file(&link);
+        std::os::unix::fs::symlink(&outside, &link).expect("create symlink");
+
+        let target = link.join("via_symlink.txt");
+        let result = check_path(&target.to_string_lossy());
+        assert!(
+            result.is_err(),
+            "a symlink inside the granted root pointing outside it must not \
+             grant access to what it points at"
+        );
+    }
+}
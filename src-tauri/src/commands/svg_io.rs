@@ -0,0 +1,605 @@
+use super::dxf_io::clamped_uniform_knots;
+use super::{scope, LoadResult, PointData, SaveResult, ShapeData};
+use lyon::math::point;
+use lyon::path::{Event, Path};
+use std::f64::consts::TAU;
+
+/// Curves are flattened to line segments within this distance (in
+/// drawing units) of the true curve, matching lyon's default notion of
+/// "close enough" for on-screen geometry.
+const FLATTEN_TOLERANCE: f32 = 0.1;
+
+/// Angular/parametric step count used to sample `arc`, `ellipse`, and
+/// `spline` shapes down to a polyline for export. SVG has no native
+/// elliptical-arc-by-angle or B-spline primitive that matches how this
+/// app stores those shapes, so exporting them means flattening, the same
+/// way `flatten_path` flattens curves on import.
+const EXPORT_SAMPLES: usize = 64;
+
+/// Export drawing to SVG format
+#[tauri::command]
+pub fn export_svg(path: String, shapes_json: String) -> SaveResult {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => return SaveResult { success: false, message },
+    };
+
+    let shapes: Vec<ShapeData> = match serde_json::from_str(&shapes_json) {
+        Ok(s) => s,
+        Err(e) => {
+            return SaveResult {
+                success: false,
+                message: format!("Failed to parse shapes: {}", e),
+            }
+        }
+    };
+
+    let mut elements = String::new();
+    let mut bbox: Option<(f64, f64, f64, f64)> = None; // min_x, min_y, max_x, max_y
+
+    for shape in &shapes {
+        let points_for_bbox = shape_points(shape);
+        for p in &points_for_bbox {
+            bbox = Some(match bbox {
+                None => (p.x, p.y, p.x, p.y),
+                Some((min_x, min_y, max_x, max_y)) => (
+                    min_x.min(p.x),
+                    min_y.min(p.y),
+                    max_x.max(p.x),
+                    max_y.max(p.y),
+                ),
+            });
+        }
+
+        let style = svg_style(shape);
+        match shape.shape_type.as_str() {
+            "line" => {
+                if let (Some(start), Some(end)) = (shape.start, shape.end) {
+                    elements.push_str(&format!(
+                        "  <line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\" {} />\n",
+                        start.x, start.y, end.x, end.y, style
+                    ));
+                }
+            }
+            "circle" => {
+                if let (Some(center), Some(radius)) = (shape.center, shape.radius) {
+                    elements.push_str(&format!(
+                        "  <circle cx=\"{}\" cy=\"{}\" r=\"{}\" {} />\n",
+                        center.x, center.y, radius, style
+                    ));
+                }
+            }
+            "polyline" => {
+                if let Some(points) = &shape.points {
+                    push_point_list(&mut elements, points, shape.closed.unwrap_or(false), &style);
+                }
+            }
+            "arc" => {
+                if let (Some(center), Some(radius), Some(start_angle), Some(end_angle)) =
+                    (shape.center, shape.radius, shape.start_angle, shape.end_angle)
+                {
+                    let points = sample_arc(center, radius, start_angle, end_angle);
+                    push_point_list(&mut elements, &points, false, &style);
+                }
+            }
+            "ellipse" => {
+                if let (Some(center), Some(major_axis), Some(ratio)) =
+                    (shape.center, shape.major_axis, shape.ratio)
+                {
+                    let start = shape.start_angle.unwrap_or(0.0);
+                    let end = shape.end_angle.unwrap_or(TAU);
+                    let points = sample_ellipse(center, major_axis, ratio, start, end);
+                    let closed = (end - start - TAU).abs() < 1e-6;
+                    push_point_list(&mut elements, &points, closed, &style);
+                }
+            }
+            "spline" => {
+                if let Some(points) = &shape.points {
+                    let degree = shape.spline_degree.unwrap_or(3) as usize;
+                    let knots = shape
+                        .spline_knots
+                        .clone()
+                        .unwrap_or_else(|| clamped_uniform_knots(degree, points.len()));
+                    let sampled = sample_spline(points, degree, &knots);
+                    push_point_list(&mut elements, &sampled, shape.closed.unwrap_or(false), &style);
+                }
+            }
+            "text" => {
+                if let (Some(anchor), Some(text)) = (&shape.text_anchor, &shape.text) {
+                    elements.push_str(&format!(
+                        "  <text x=\"{}\" y=\"{}\" {}>{}</text>\n",
+                        anchor.x,
+                        anchor.y,
+                        style,
+                        escape_text(text)
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let (min_x, min_y, max_x, max_y) = bbox.unwrap_or((0.0, 0.0, 0.0, 0.0));
+    let width = (max_x - min_x).max(0.0);
+    let height = (max_y - min_y).max(0.0);
+
+    let svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"{} {} {} {}\">\n{}</svg>\n",
+        min_x, min_y, width, height, elements
+    );
+
+    match fs_write(&checked, &svg) {
+        Ok(_) => SaveResult {
+            success: true,
+            message: format!("SVG exported to {}", path),
+        },
+        Err(e) => SaveResult {
+            success: false,
+            message: format!("Failed to export SVG: {}", e),
+        },
+    }
+}
+
+/// Import drawing from SVG format, flattening curves into polylines.
+#[tauri::command]
+pub fn import_svg(path: String) -> LoadResult {
+    let checked = match scope::check_path(&path) {
+        Ok(p) => p,
+        Err(message) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message,
+            }
+        }
+    };
+
+    let content = match std::fs::read_to_string(&checked) {
+        Ok(c) => c,
+        Err(e) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message: format!("Failed to read SVG: {}", e),
+            }
+        }
+    };
+
+    let document = match roxmltree::Document::parse(&content) {
+        Ok(d) => d,
+        Err(e) => {
+            return LoadResult {
+                success: false,
+                data: None,
+                message: format!("Failed to parse SVG: {}", e),
+            }
+        }
+    };
+
+    let mut shapes: Vec<ShapeData> = Vec::new();
+
+    for node in document.descendants() {
+        match node.tag_name().name() {
+            "line" => {
+                let (x1, y1, x2, y2) = (
+                    attr_f64(&node, "x1"),
+                    attr_f64(&node, "y1"),
+                    attr_f64(&node, "x2"),
+                    attr_f64(&node, "y2"),
+                );
+                shapes.push(blank_shape("line", |s| {
+                    s.start = Some(PointData { x: x1, y: y1 });
+                    s.end = Some(PointData { x: x2, y: y2 });
+                }));
+            }
+            "circle" => {
+                let (cx, cy, r) = (attr_f64(&node, "cx"), attr_f64(&node, "cy"), attr_f64(&node, "r"));
+                shapes.push(blank_shape("circle", |s| {
+                    s.center = Some(PointData { x: cx, y: cy });
+                    s.radius = Some(r);
+                }));
+            }
+            "rect" => {
+                let (x, y, w, h) = (
+                    attr_f64(&node, "x"),
+                    attr_f64(&node, "y"),
+                    attr_f64(&node, "width"),
+                    attr_f64(&node, "height"),
+                );
+                shapes.push(blank_shape("polyline", |s| {
+                    s.points = Some(vec![
+                        PointData { x, y },
+                        PointData { x: x + w, y },
+                        PointData { x: x + w, y: y + h },
+                        PointData { x, y: y + h },
+                        PointData { x, y },
+                    ]);
+                }));
+            }
+            "path" => {
+                if let Some(d) = node.attribute("d") {
+                    for points in flatten_path(d) {
+                        shapes.push(blank_shape("polyline", |s| {
+                            s.points = Some(points.clone());
+                        }));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    match serde_json::to_string(&shapes) {
+        Ok(json) => LoadResult {
+            success: true,
+            data: Some(json),
+            message: "SVG imported successfully".to_string(),
+        },
+        Err(e) => LoadResult {
+            success: false,
+            data: None,
+            message: format!("Failed to serialize shapes: {}", e),
+        },
+    }
+}
+
+/// Parses an SVG path `d` attribute and flattens every subpath (straight
+/// or curved) into a polyline of points, using lyon to tessellate the
+/// cubic/quadratic/arc segments.
+///
+/// `svgtypes::PathParser` is a raw tokenizer: it hands back each
+/// segment's coordinates exactly as written, plus an `abs` flag, and
+/// leaves resolving relative (lowercase-command) coordinates against the
+/// current point up to the caller. This function does that resolution,
+/// and also reconstructs the `S`/`T` "smooth" control point as the
+/// reflection of the previous segment's last control point, per the SVG
+/// spec, rather than degenerating it to the current point.
+fn flatten_path(d: &str) -> Vec<Vec<PointData>> {
+    let Ok(svg_path) = svgtypes::PathParser::from(d).collect::<Result<Vec<_>, _>>() else {
+        return Vec::new();
+    };
+
+    let mut builder = Path::builder();
+    let mut cursor = point(0.0, 0.0);
+    let mut subpath_start = cursor;
+    let mut has_current = false;
+    // Last control point of a preceding cubic/quadratic segment, used to
+    // compute the reflected control point for a following `S`/`T`. Reset
+    // to `None` by any segment that isn't itself a cubic/quadratic, per
+    // spec (an `S`/`T` with no preceding curve uses the current point).
+    let mut last_cubic_ctrl: Option<lyon::math::Point> = None;
+    let mut last_quad_ctrl: Option<lyon::math::Point> = None;
+
+    let resolve = |cursor: lyon::math::Point, x: f64, y: f64, abs: bool| {
+        if abs {
+            point(x as f32, y as f32)
+        } else {
+            point(cursor.x + x as f32, cursor.y + y as f32)
+        }
+    };
+
+    for segment in svg_path {
+        use svgtypes::PathSegment::*;
+        match segment {
+            MoveTo { abs, x, y } => {
+                if has_current {
+                    builder.end(false);
+                }
+                cursor = resolve(cursor, x, y, abs);
+                subpath_start = cursor;
+                builder.begin(cursor);
+                has_current = true;
+                last_cubic_ctrl = None;
+                last_quad_ctrl = None;
+            }
+            LineTo { abs, x, y } => {
+                cursor = resolve(cursor, x, y, abs);
+                builder.line_to(cursor);
+                last_cubic_ctrl = None;
+                last_quad_ctrl = None;
+            }
+            HorizontalLineTo { abs, x } => {
+                cursor = if abs {
+                    point(x as f32, cursor.y)
+                } else {
+                    point(cursor.x + x as f32, cursor.y)
+                };
+                builder.line_to(cursor);
+                last_cubic_ctrl = None;
+                last_quad_ctrl = None;
+            }
+            VerticalLineTo { abs, y } => {
+                cursor = if abs {
+                    point(cursor.x, y as f32)
+                } else {
+                    point(cursor.x, cursor.y + y as f32)
+                };
+                builder.line_to(cursor);
+                last_cubic_ctrl = None;
+                last_quad_ctrl = None;
+            }
+            CurveTo {
+                abs,
+                x1,
+                y1,
+                x2,
+                y2,
+                x,
+                y,
+            } => {
+                let ctrl1 = resolve(cursor, x1, y1, abs);
+                let ctrl2 = resolve(cursor, x2, y2, abs);
+                let to = resolve(cursor, x, y, abs);
+                builder.cubic_bezier_to(ctrl1, ctrl2, to);
+                cursor = to;
+                last_cubic_ctrl = Some(ctrl2);
+                last_quad_ctrl = None;
+            }
+            SmoothCurveTo { abs, x2, y2, x, y } => {
+                let ctrl1 = last_cubic_ctrl
+                    .map(|prev| point(2.0 * cursor.x - prev.x, 2.0 * cursor.y - prev.y))
+                    .unwrap_or(cursor);
+                let ctrl2 = resolve(cursor, x2, y2, abs);
+                let to = resolve(cursor, x, y, abs);
+                builder.cubic_bezier_to(ctrl1, ctrl2, to);
+                cursor = to;
+                last_cubic_ctrl = Some(ctrl2);
+                last_quad_ctrl = None;
+            }
+            Quadratic { abs, x1, y1, x, y } => {
+                let ctrl = resolve(cursor, x1, y1, abs);
+                let to = resolve(cursor, x, y, abs);
+                builder.quadratic_bezier_to(ctrl, to);
+                cursor = to;
+                last_quad_ctrl = Some(ctrl);
+                last_cubic_ctrl = None;
+            }
+            SmoothQuadratic { abs, x, y } => {
+                let ctrl = last_quad_ctrl
+                    .map(|prev| point(2.0 * cursor.x - prev.x, 2.0 * cursor.y - prev.y))
+                    .unwrap_or(cursor);
+                let to = resolve(cursor, x, y, abs);
+                builder.quadratic_bezier_to(ctrl, to);
+                cursor = to;
+                last_quad_ctrl = Some(ctrl);
+                last_cubic_ctrl = None;
+            }
+            EllipticalArc {
+                abs,
+                rx,
+                ry,
+                x_axis_rotation,
+                large_arc,
+                sweep,
+                x,
+                y,
+            } => {
+                let to = resolve(cursor, x, y, abs);
+                let arc = lyon::geom::SvgArc {
+                    from: cursor,
+                    to,
+                    radii: lyon::math::vector(rx as f32, ry as f32),
+                    x_rotation: lyon::math::Angle::degrees(x_axis_rotation as f32),
+                    flags: lyon::path::ArcFlags { large_arc, sweep },
+                };
+                arc.for_each_quadratic_bezier(&mut |q| {
+                    builder.quadratic_bezier_to(q.ctrl, q.to);
+                });
+                cursor = to;
+                last_cubic_ctrl = None;
+                last_quad_ctrl = None;
+            }
+            ClosePath { .. } => {
+                builder.close();
+                has_current = false;
+                cursor = subpath_start;
+                last_cubic_ctrl = None;
+                last_quad_ctrl = None;
+            }
+        }
+    }
+    if has_current {
+        builder.end(false);
+    }
+
+    flatten_and_collect(&builder.build())
+}
+
+fn flatten_and_collect(path: &Path) -> Vec<Vec<PointData>> {
+    let mut subpaths: Vec<Vec<PointData>> = Vec::new();
+    let mut current: Vec<PointData> = Vec::new();
+
+    for event in path.iter().flattened(FLATTEN_TOLERANCE) {
+        match event {
+            Event::Begin { at } => {
+                current = vec![PointData {
+                    x: at.x as f64,
+                    y: at.y as f64,
+                }];
+            }
+            Event::Line { to, .. } => current.push(PointData {
+                x: to.x as f64,
+                y: to.y as f64,
+            }),
+            Event::End { close, .. } => {
+                if close {
+                    if let Some(first) = current.first().copied() {
+                        current.push(first);
+                    }
+                }
+                if current.len() > 1 {
+                    subpaths.push(std::mem::take(&mut current));
+                }
+            }
+            _ => {}
+        }
+    }
+    subpaths
+}
+
+fn shape_points(shape: &ShapeData) -> Vec<PointData> {
+    let mut pts = Vec::new();
+    pts.extend(shape.start);
+    pts.extend(shape.end);
+    if let (Some(center), Some(radius)) = (shape.center, shape.radius) {
+        pts.push(PointData {
+            x: center.x - radius,
+            y: center.y - radius,
+        });
+        pts.push(PointData {
+            x: center.x + radius,
+            y: center.y + radius,
+        });
+    }
+    if let Some(points) = &shape.points {
+        pts.extend(points.iter().copied());
+    }
+    if let Some(anchor) = shape.text_anchor {
+        pts.push(anchor);
+    }
+    if shape.shape_type == "ellipse" {
+        if let (Some(center), Some(major_axis), Some(ratio)) =
+            (shape.center, shape.major_axis, shape.ratio)
+        {
+            let start = shape.start_angle.unwrap_or(0.0);
+            let end = shape.end_angle.unwrap_or(TAU);
+            pts.extend(sample_ellipse(center, major_axis, ratio, start, end));
+        }
+    }
+    pts
+}
+
+/// Writes a `points`-list element for a polyline-shaped export: a closed
+/// loop becomes `<polygon>` (which SVG auto-closes back to the first
+/// point), matching how the shape is actually drawn on the canvas instead
+/// of always emitting an open `<polyline>`.
+fn push_point_list(elements: &mut String, points: &[PointData], closed: bool, style: &str) {
+    let pairs = points
+        .iter()
+        .map(|p| format!("{},{}", p.x, p.y))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let tag = if closed { "polygon" } else { "polyline" };
+    elements.push_str(&format!("  <{} points=\"{}\" {} />\n", tag, pairs, style));
+}
+
+/// Samples a circular arc (as stored for `arc` shapes: center, radius,
+/// start/end angle in radians) into points along its curve.
+fn sample_arc(center: PointData, radius: f64, start_angle: f64, end_angle: f64) -> Vec<PointData> {
+    (0..=EXPORT_SAMPLES)
+        .map(|i| {
+            let t = start_angle + (end_angle - start_angle) * (i as f64 / EXPORT_SAMPLES as f64);
+            PointData {
+                x: center.x + radius * t.cos(),
+                y: center.y + radius * t.sin(),
+            }
+        })
+        .collect()
+}
+
+/// Samples an ellipse (as stored for `ellipse` shapes: center, the major
+/// axis endpoint relative to center, and the minor/major ratio) over a
+/// parameter range, the same parametrization DXF's `ELLIPSE` entity uses.
+fn sample_ellipse(
+    center: PointData,
+    major_axis: PointData,
+    ratio: f64,
+    start_param: f64,
+    end_param: f64,
+) -> Vec<PointData> {
+    let minor_axis = PointData {
+        x: -major_axis.y * ratio,
+        y: major_axis.x * ratio,
+    };
+    (0..=EXPORT_SAMPLES)
+        .map(|i| {
+            let t = start_param + (end_param - start_param) * (i as f64 / EXPORT_SAMPLES as f64);
+            PointData {
+                x: center.x + major_axis.x * t.cos() + minor_axis.x * t.sin(),
+                y: center.y + major_axis.y * t.cos() + minor_axis.y * t.sin(),
+            }
+        })
+        .collect()
+}
+
+/// Samples a B-spline's actual curve (not just its control polygon) via
+/// de Boor's algorithm, using `control_points`/`degree`/`knots` the same
+/// way `dxf_io` interprets a `SPLINE` entity.
+fn sample_spline(control_points: &[PointData], degree: usize, knots: &[f64]) -> Vec<PointData> {
+    if control_points.len() <= degree || knots.len() < control_points.len() + degree + 1 {
+        return control_points.to_vec();
+    }
+
+    let n = control_points.len() - 1;
+    let t_min = knots[degree];
+    let t_max = knots[n + 1];
+    if t_max <= t_min {
+        return control_points.to_vec();
+    }
+
+    (0..=EXPORT_SAMPLES)
+        .map(|i| {
+            let t = t_min + (t_max - t_min) * (i as f64 / EXPORT_SAMPLES as f64);
+            de_boor(t.min(t_max), degree, control_points, knots)
+        })
+        .collect()
+}
+
+/// De Boor's algorithm: evaluates a clamped B-spline of the given degree
+/// at parameter `t`, returning the point on the curve rather than one of
+/// its control points.
+fn de_boor(t: f64, degree: usize, control_points: &[PointData], knots: &[f64]) -> PointData {
+    let last_span = control_points.len() - 1;
+    let span = (degree..=last_span)
+        .find(|&i| t < knots[i + 1])
+        .unwrap_or(last_span);
+
+    let mut d: Vec<PointData> = (0..=degree)
+        .map(|j| control_points[span - degree + j])
+        .collect();
+
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let i = span - degree + j;
+            let denom = knots[i + degree - r + 1] - knots[i];
+            let alpha = if denom.abs() < 1e-9 { 0.0 } else { (t - knots[i]) / denom };
+            d[j] = PointData {
+                x: (1.0 - alpha) * d[j - 1].x + alpha * d[j].x,
+                y: (1.0 - alpha) * d[j - 1].y + alpha * d[j].y,
+            };
+        }
+    }
+    d[degree]
+}
+
+fn svg_style(shape: &ShapeData) -> String {
+    match shape.color {
+        Some([r, g, b]) => format!("style=\"stroke: rgb({},{},{}); fill: none\"", r, g, b),
+        None => "style=\"stroke: black; fill: none\"".to_string(),
+    }
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn attr_f64(node: &roxmltree::Node, name: &str) -> f64 {
+    node.attribute(name)
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0.0)
+}
+
+fn blank_shape(shape_type: &str, build: impl FnOnce(&mut ShapeData)) -> ShapeData {
+    let mut shape = ShapeData {
+        shape_type: shape_type.to_string(),
+        ..Default::default()
+    };
+    build(&mut shape);
+    shape
+}
+
+fn fs_write(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    std::fs::write(path, content)
+}
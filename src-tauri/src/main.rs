@@ -3,16 +3,36 @@
 
 mod commands;
 
-use commands::{save_file, load_file, export_dxf, import_dxf};
+use commands::{
+    export_dxf, export_svg, grant_startup_scope, import_dxf, import_svg, load_file,
+    load_project_binary, open_directory_scope, revoke_directory_scope, save_file,
+    save_project_binary,
+};
 
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .plugin(tauri_plugin_dialog::init())
+        .setup(|_app| {
+            // The current project directory is always in scope; anything
+            // else requires an explicit grant via open_directory_scope,
+            // which only the native folder picker can supply a path for.
+            if let Ok(cwd) = std::env::current_dir() {
+                let _ = grant_startup_scope(&cwd);
+            }
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             save_file,
             load_file,
             export_dxf,
-            import_dxf
+            import_dxf,
+            export_svg,
+            import_svg,
+            save_project_binary,
+            load_project_binary,
+            open_directory_scope,
+            revoke_directory_scope
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");